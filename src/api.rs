@@ -6,6 +6,9 @@ use std::path::Path;
 
 use io::BufReader;
 
+use crate::expr::Expr;
+use crate::row::{Datum, Row};
+
 fn read_lines<P>(filename: P) -> io::Result<io::Lines<io::BufReader<File>>>
 where
     P: AsRef<Path>,
@@ -37,6 +40,97 @@ trait RDDBase {
             source: self,
         }
     }
+
+    fn filter<F>(self, predfn: F) -> FilterRDD<F, Self>
+    where
+        Self: Sized,
+        F: FnMut(&Self::Item) -> bool,
+    {
+        FilterRDD {
+            predfn,
+            source: self,
+        }
+    }
+
+    fn flat_map<F, U, I>(self, mapfn: F) -> FlatMapRDD<F, Self, I>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item) -> I,
+        I: IntoIterator<Item = U>,
+    {
+        FlatMapRDD {
+            mapfn,
+            source: self,
+            current: None,
+        }
+    }
+
+    fn reduce<F>(mut self, mut reducefn: F) -> Option<Self::Item>
+    where
+        Self: Sized,
+        F: FnMut(Self::Item, Self::Item) -> Self::Item,
+    {
+        let mut acc = self.next()?;
+        while let Some(item) = self.next() {
+            acc = reducefn(acc, item);
+        }
+        Some(acc)
+    }
+
+    fn fold<B, F>(mut self, init: B, mut foldfn: F) -> B
+    where
+        Self: Sized,
+        F: FnMut(B, Self::Item) -> B,
+    {
+        let mut acc = init;
+        while let Some(item) = self.next() {
+            acc = foldfn(acc, item);
+        }
+        acc
+    }
+
+    fn count(mut self) -> usize
+    where
+        Self: Sized,
+    {
+        let mut n = 0;
+        while self.next().is_some() {
+            n += 1;
+        }
+        n
+    }
+
+    fn collect(mut self) -> Vec<Self::Item>
+    where
+        Self: Sized,
+    {
+        let mut v = Vec::new();
+        while let Some(item) = self.next() {
+            v.push(item);
+        }
+        v
+    }
+
+    /// Like `filter`, but the predicate is an `Expr` evaluated against each `Row` rather than
+    /// a Rust closure. A row is kept only when the predicate evaluates to `Datum::BOOL(true)`;
+    /// `false`, `NULL`, and eval errors all drop the row.
+    fn filter_expr(self, pred: Expr) -> ExprFilterRDD<Self>
+    where
+        Self: Sized + RDDBase<Item = Row>,
+    {
+        ExprFilterRDD { pred, source: self }
+    }
+
+    /// Evaluates `exprs` against each input `Row` to produce a new, projected `Row`.
+    fn select(self, exprs: Vec<Expr>) -> ProjectRDD<Self>
+    where
+        Self: Sized + RDDBase<Item = Row>,
+    {
+        ProjectRDD {
+            exprs,
+            source: self,
+        }
+    }
 }
 
 struct MapRDD<F, R> {
@@ -52,7 +146,100 @@ where
     type Item = U;
 
     fn next(&mut self) -> Option<U> {
-        unimplemented!()
+        self.source.next().map(|item| (self.mapfn)(item))
+    }
+}
+
+struct FilterRDD<F, R> {
+    predfn: F,
+    source: R,
+}
+
+impl<F, R> RDDBase for FilterRDD<F, R>
+where
+    R: RDDBase,
+    F: FnMut(&R::Item) -> bool,
+{
+    type Item = R::Item;
+
+    fn next(&mut self) -> Option<R::Item> {
+        while let Some(item) = self.source.next() {
+            if (self.predfn)(&item) {
+                return Some(item);
+            }
+        }
+        None
+    }
+}
+
+struct FlatMapRDD<F, R, I: IntoIterator> {
+    mapfn: F,
+    source: R,
+    current: Option<I::IntoIter>,
+}
+
+impl<F, R, U, I> RDDBase for FlatMapRDD<F, R, I>
+where
+    R: RDDBase,
+    F: FnMut(R::Item) -> I,
+    I: IntoIterator<Item = U>,
+{
+    type Item = U;
+
+    fn next(&mut self) -> Option<U> {
+        loop {
+            if let Some(it) = self.current.as_mut() {
+                if let Some(item) = it.next() {
+                    return Some(item);
+                }
+                self.current = None;
+            }
+            let item = self.source.next()?;
+            self.current = Some((self.mapfn)(item).into_iter());
+        }
+    }
+}
+
+struct ExprFilterRDD<R> {
+    pred: Expr,
+    source: R,
+}
+
+impl<R> RDDBase for ExprFilterRDD<R>
+where
+    R: RDDBase<Item = Row>,
+{
+    type Item = Row;
+
+    fn next(&mut self) -> Option<Row> {
+        while let Some(row) = self.source.next() {
+            if matches!(self.pred.eval(&row), Ok(Datum::BOOL(true))) {
+                return Some(row);
+            }
+        }
+        None
+    }
+}
+
+struct ProjectRDD<R> {
+    exprs: Vec<Expr>,
+    source: R,
+}
+
+impl<R> RDDBase for ProjectRDD<R>
+where
+    R: RDDBase<Item = Row>,
+{
+    type Item = Row;
+
+    fn next(&mut self) -> Option<Row> {
+        let row = self.source.next()?;
+        let cols = self
+            .exprs
+            .iter()
+            .map(|e| e.eval(&row).unwrap_or(Datum::NULL))
+            .collect();
+        Some(Row::new(cols))
     }
 }
 
@@ -93,4 +280,66 @@ fn test() {
     }
 }
 
+/// An in-memory RDD over a `Vec`, used by tests that need a deterministic source instead of
+/// whatever lines happen to be in a file on disk.
+#[cfg(test)]
+struct VecRDD<T> {
+    iter: std::vec::IntoIter<T>,
+}
+
+#[cfg(test)]
+impl<T> RDDBase for VecRDD<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.iter.next()
+    }
+}
+
+#[cfg(test)]
+fn vec_rdd<T>(items: Vec<T>) -> VecRDD<T> {
+    VecRDD {
+        iter: items.into_iter(),
+    }
+}
+
+#[test]
+fn test_combinators() {
+    // [1, 2, 3, 4, 5] -> filter > 2 -> [3, 4, 5] -> flat_map(n, n) -> [3, 3, 4, 4, 5, 5] -> sum.
+    let total: i32 = vec_rdd(vec![1, 2, 3, 4, 5])
+        .filter(|n| *n > 2)
+        .flat_map(|n| vec![n, n])
+        .fold(0, |acc, n| acc + n);
+
+    assert_eq!(total, 2 * (3 + 4 + 5));
+}
+
+#[test]
+fn test_filter_expr() {
+    use crate::expr::{Expr::*, RelOp};
+
+    let rows = vec![
+        Row::new(vec![Datum::INT(10)]),
+        Row::new(vec![Datum::INT(50)]),
+        Row::new(vec![Datum::INT(20)]),
+        Row::new(vec![Datum::INT(60)]),
+    ];
+
+    let kept = vec_rdd(rows)
+        .filter_expr(RelExpr(
+            Box::new(CID(0)),
+            RelOp::Gt,
+            Box::new(Literal(Datum::INT(30))),
+        ))
+        .collect();
+
+    assert_eq!(
+        kept,
+        vec![
+            Row::new(vec![Datum::INT(50)]),
+            Row::new(vec![Datum::INT(60)]),
+        ]
+    );
+}
+
 fn foo(a: i32, b: i32) {}
@@ -77,6 +77,7 @@ pub enum Expr {
     Literal(Datum),
     ArithExpr(Box<Expr>, ArithOp, Box<Expr>),
     RelExpr(Box<Expr>, RelOp, Box<Expr>),
+    LogExpr(Box<Expr>, LogOp, Option<Box<Expr>>),
 }
 
 impl fmt::Display for Expr {
@@ -86,6 +87,44 @@ impl fmt::Display for Expr {
             Literal(v) => write!(f, "{}", v),
             ArithExpr(lhs, op, rhs) => write!(f, "({} {} {})", lhs, op, rhs),
             RelExpr(lhs, op, rhs) => write!(f, "({} {} {})", lhs, op, rhs),
+            LogExpr(lhs, op, Some(rhs)) => write!(f, "({} {} {})", lhs, op, rhs),
+            LogExpr(lhs, op, None) => write!(f, "({} {})", op, lhs),
+        }
+    }
+}
+
+impl Expr {
+    /// Renders the expression as an indented ASCII tree, one node per line, for dumping
+    /// query plans. `Display` stays a single parenthesized line meant for inline use; this
+    /// is for reading deep trees.
+    pub fn explain(&self) -> String {
+        let mut out = String::new();
+        self.explain_node(0, &mut out);
+        out
+    }
+
+    fn explain_node(&self, depth: usize, out: &mut String) {
+        let indent = "  ".repeat(depth);
+        match self {
+            CID(cid) => out.push_str(&format!("{}CID {}\n", indent, cid)),
+            Literal(v) => out.push_str(&format!("{}Literal {}\n", indent, v)),
+            ArithExpr(lhs, op, rhs) => {
+                out.push_str(&format!("{}ArithExpr {}\n", indent, op));
+                lhs.explain_node(depth + 1, out);
+                rhs.explain_node(depth + 1, out);
+            }
+            RelExpr(lhs, op, rhs) => {
+                out.push_str(&format!("{}RelExpr {}\n", indent, op));
+                lhs.explain_node(depth + 1, out);
+                rhs.explain_node(depth + 1, out);
+            }
+            LogExpr(lhs, op, rhs) => {
+                out.push_str(&format!("{}LogExpr {}\n", indent, op));
+                lhs.explain_node(depth + 1, out);
+                if let Some(rhs) = rhs {
+                    rhs.explain_node(depth + 1, out);
+                }
+            }
         }
     }
 }
@@ -123,38 +162,237 @@ impl ops::Div for Expr {
     }
 }
 
+/// An operand-type mismatch or a division by zero encountered while evaluating an `Expr` against
+/// a `Row`. Replaces the evaluator's former `panic!`s so callers can recover from bad data
+/// instead of aborting the whole process.
+#[derive(Debug, Clone, PartialEq)]
+pub enum EvalError {
+    IncompatibleOperands(String),
+    DivideByZero,
+}
+
+impl fmt::Display for EvalError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EvalError::IncompatibleOperands(msg) => write!(f, "incompatible operands: {}", msg),
+            EvalError::DivideByZero => write!(f, "division by zero"),
+        }
+    }
+}
+
+fn eval_arith(b1: Datum, op: &ArithOp, b2: Datum) -> Result<Datum, EvalError> {
+    match (b1, b2) {
+        (Datum::INT(i1), Datum::INT(i2)) => match op {
+            ArithOp::Add => Ok(Datum::INT(i1 + i2)),
+            ArithOp::Sub => Ok(Datum::INT(i1 - i2)),
+            ArithOp::Mul => Ok(Datum::INT(i1 * i2)),
+            ArithOp::Div if i2 == 0 => Err(EvalError::DivideByZero),
+            ArithOp::Div => Ok(Datum::INT(i1 / i2)),
+        },
+        (Datum::INT(i1), Datum::FLOAT(f2)) => eval_arith_float(i1 as f64, op, f2),
+        (Datum::FLOAT(f1), Datum::INT(i2)) => eval_arith_float(f1, op, i2 as f64),
+        (Datum::FLOAT(f1), Datum::FLOAT(f2)) => eval_arith_float(f1, op, f2),
+        (b1, b2) => Err(EvalError::IncompatibleOperands(format!(
+            "{:?} {} {:?}",
+            b1, op, b2
+        ))),
+    }
+}
+
+fn eval_arith_float(f1: f64, op: &ArithOp, f2: f64) -> Result<Datum, EvalError> {
+    let res = match op {
+        ArithOp::Add => f1 + f2,
+        ArithOp::Sub => f1 - f2,
+        ArithOp::Mul => f1 * f2,
+        ArithOp::Div if f2 == 0.0 => return Err(EvalError::DivideByZero),
+        ArithOp::Div => f1 / f2,
+    };
+    Ok(Datum::FLOAT(res))
+}
+
+fn apply_rel<T: PartialOrd>(a: T, op: &RelOp, b: T) -> bool {
+    match op {
+        RelOp::Eq => a == b,
+        RelOp::Ne => a != b,
+        RelOp::Lt => a < b,
+        RelOp::Le => a <= b,
+        RelOp::Gt => a > b,
+        RelOp::Ge => a >= b,
+    }
+}
+
+fn eval_rel(b1: Datum, op: &RelOp, b2: Datum) -> Result<Datum, EvalError> {
+    let res = match (b1, b2) {
+        (Datum::INT(i1), Datum::INT(i2)) => apply_rel(i1, op, i2),
+        (Datum::INT(i1), Datum::FLOAT(f2)) => apply_rel(i1 as f64, op, f2),
+        (Datum::FLOAT(f1), Datum::INT(i2)) => apply_rel(f1, op, i2 as f64),
+        (Datum::FLOAT(f1), Datum::FLOAT(f2)) => apply_rel(f1, op, f2),
+        (Datum::STRING(s1), Datum::STRING(s2)) => apply_rel(s1, op, s2),
+        (Datum::BOOL(b1), Datum::BOOL(b2)) => apply_rel(b1, op, b2),
+        (b1, b2) => {
+            return Err(EvalError::IncompatibleOperands(format!(
+                "{:?} {} {:?}",
+                b1, op, b2
+            )))
+        }
+    };
+    Ok(Datum::BOOL(res))
+}
+
 impl Expr {
-    pub fn eval<'a>(&'a self, row: &'a Row) -> Datum {
+    pub fn eval<'a>(&'a self, row: &'a Row) -> Result<Datum, EvalError> {
         match self {
-            CID(cid) => row.get_column(*cid).clone(),
-            Literal(lit) => lit.clone(),
+            CID(cid) => Ok(row.get_column(*cid).clone()),
+            Literal(lit) => Ok(lit.clone()),
             ArithExpr(b1, op, b2) => {
-                let b1 = b1.eval(row);
-                let b2 = b2.eval(row);
-                let res = match (b1, op, b2) {
-                    (Datum::INT(i1), ArithOp::Add, Datum::INT(i2)) => i1 + i2,
-                    (Datum::INT(i1), ArithOp::Sub, Datum::INT(i2)) => i1 - i2,
-                    (Datum::INT(i1), ArithOp::Mul, Datum::INT(i2)) => i1 * i2,
-                    (Datum::INT(i1), ArithOp::Div, Datum::INT(i2)) => i1 / i2,
-                    _ => panic!("Internal error: Operands of ArithOp not resolved yet."),
-                };
-                Datum::INT(res)
+                let b1 = b1.eval(row)?;
+                let b2 = b2.eval(row)?;
+                if matches!(b1, Datum::NULL) || matches!(b2, Datum::NULL) {
+                    return Ok(Datum::NULL);
+                }
+                eval_arith(b1, op, b2)
             }
             RelExpr(b1, op, b2) => {
-                let b1 = b1.eval(row);
-                let b2 = b2.eval(row);
-                let res = match (b1, op, b2) {
-                    (Datum::INT(i1), RelOp::Eq, Datum::INT(i2)) => i1 == i2,
-                    (Datum::INT(i1), RelOp::Ne, Datum::INT(i2)) => i1 != i2,
-                    (Datum::INT(i1), RelOp::Le, Datum::INT(i2)) => i1 <= i2,
-                    (Datum::INT(i1), RelOp::Lt, Datum::INT(i2)) => i1 < i2,
-                    (Datum::INT(i1), RelOp::Ge, Datum::INT(i2)) => i1 >= i2,
-                    (Datum::INT(i1), RelOp::Gt, Datum::INT(i2)) => i1 > i2,
-                    _ => panic!("Internal error: Operands of RelOp not resolved yet."),
-                };
-                Datum::BOOL(res)
+                let b1 = b1.eval(row)?;
+                let b2 = b2.eval(row)?;
+                if matches!(b1, Datum::NULL) || matches!(b2, Datum::NULL) {
+                    return Ok(Datum::NULL);
+                }
+                eval_rel(b1, op, b2)
+            }
+            // Three-valued (SQL) logic: NULL propagates except where it's absorbed by a
+            // short-circuiting FALSE (AND) or TRUE (OR) on the other side.
+            LogExpr(lhs, LogOp::Not, None) => match lhs.eval(row)? {
+                Datum::BOOL(b) => Ok(Datum::BOOL(!b)),
+                Datum::NULL => Ok(Datum::NULL),
+                other => Err(EvalError::IncompatibleOperands(format!("NOT {:?}", other))),
+            },
+            LogExpr(lhs, LogOp::And, Some(rhs)) => match lhs.eval(row)? {
+                Datum::BOOL(false) => Ok(Datum::BOOL(false)),
+                Datum::BOOL(true) => rhs.eval(row),
+                Datum::NULL => match rhs.eval(row)? {
+                    Datum::BOOL(false) => Ok(Datum::BOOL(false)),
+                    Datum::BOOL(true) | Datum::NULL => Ok(Datum::NULL),
+                    other => Err(EvalError::IncompatibleOperands(format!("AND {:?}", other))),
+                },
+                other => Err(EvalError::IncompatibleOperands(format!("{:?} AND", other))),
+            },
+            LogExpr(lhs, LogOp::Or, Some(rhs)) => match lhs.eval(row)? {
+                Datum::BOOL(true) => Ok(Datum::BOOL(true)),
+                Datum::BOOL(false) => rhs.eval(row),
+                Datum::NULL => match rhs.eval(row)? {
+                    Datum::BOOL(true) => Ok(Datum::BOOL(true)),
+                    Datum::BOOL(false) | Datum::NULL => Ok(Datum::NULL),
+                    other => Err(EvalError::IncompatibleOperands(format!("OR {:?}", other))),
+                },
+                other => Err(EvalError::IncompatibleOperands(format!("{:?} OR", other))),
+            },
+            LogExpr(_, LogOp::Not, Some(_)) | LogExpr(_, LogOp::And | LogOp::Or, None) => {
+                panic!("Internal error: LogExpr arity does not match LogOp.")
+            }
+        }
+    }
+}
+
+fn is_zero(d: &Datum) -> bool {
+    matches!(d, Datum::INT(0)) || matches!(d, Datum::FLOAT(f) if *f == 0.0)
+}
+
+fn is_one(d: &Datum) -> bool {
+    matches!(d, Datum::INT(1)) || matches!(d, Datum::FLOAT(f) if *f == 1.0)
+}
+
+/// Evaluates `e` (which must be CID-free) against an empty row and collapses it to a
+/// `Literal`. Falls back to leaving `e` untouched if evaluation fails (e.g. divide-by-zero),
+/// so a runtime error is still raised at the original expression's eval site rather than here.
+fn fold_literal(e: Expr) -> Expr {
+    let empty = Row::new(vec![]);
+    match e.eval(&empty) {
+        Ok(d) => Literal(d),
+        Err(_) => e,
+    }
+}
+
+impl Expr {
+    /// Bottom-up constant folding and algebraic simplification. Folds children first, then
+    /// collapses the current node: subtrees made entirely of `Literal`s evaluate down to a
+    /// single `Literal` (so `x * 0` folds to `0` only when `x` is itself a literal), and
+    /// identities (`x + 0`, `x * 1`, `true && x`, `false || x`, double `Not`) rewrite
+    /// structurally even when `x` still contains a `CID` and can't be evaluated. `CID` nodes,
+    /// and any node containing one, are otherwise left intact.
+    pub fn fold_constants(self) -> Expr {
+        match self {
+            CID(_) | Literal(_) => self,
+            ArithExpr(b1, op, b2) => {
+                let b1 = b1.fold_constants();
+                let b2 = b2.fold_constants();
+                match (&b1, &op, &b2) {
+                    // `x + 0` / `x * 1` fold away the identity element and hand back `x`
+                    // unevaluated, so any error lurking in `x` still surfaces when it's later
+                    // evaluated for real. A `* 0` fold can't do this safely: it would have to
+                    // discard `x` and replace the whole node with the literal `0`, silently
+                    // swallowing any error `x` (or combining it with `0`) would have raised
+                    // (e.g. `$0 * 0` with a STRING column, or `(1 / 0) * 0`). So `* 0` is only
+                    // folded once both sides are literals, below, where full evaluation via
+                    // `fold_literal` still surfaces that error instead of hiding it.
+                    (Literal(d), ArithOp::Add, _) if is_zero(d) => b2,
+                    (_, ArithOp::Add, Literal(d)) if is_zero(d) => b1,
+                    (Literal(d), ArithOp::Mul, _) if is_one(d) => b2,
+                    (_, ArithOp::Mul, Literal(d)) if is_one(d) => b1,
+                    (Literal(_), _, Literal(_)) => {
+                        fold_literal(ArithExpr(Box::new(b1), op, Box::new(b2)))
+                    }
+                    _ => ArithExpr(Box::new(b1), op, Box::new(b2)),
+                }
+            }
+            RelExpr(b1, op, b2) => {
+                let b1 = b1.fold_constants();
+                let b2 = b2.fold_constants();
+                match (&b1, &b2) {
+                    (Literal(_), Literal(_)) => {
+                        fold_literal(RelExpr(Box::new(b1), op, Box::new(b2)))
+                    }
+                    _ => RelExpr(Box::new(b1), op, Box::new(b2)),
+                }
+            }
+            LogExpr(lhs, LogOp::Not, None) => {
+                let lhs = lhs.fold_constants();
+                match lhs {
+                    // !(!x) -> x
+                    LogExpr(inner, LogOp::Not, None) => *inner,
+                    Literal(_) => fold_literal(LogExpr(Box::new(lhs), LogOp::Not, None)),
+                    _ => LogExpr(Box::new(lhs), LogOp::Not, None),
+                }
+            }
+            LogExpr(lhs, LogOp::And, Some(rhs)) => {
+                let lhs = lhs.fold_constants();
+                let rhs = rhs.fold_constants();
+                match &lhs {
+                    Literal(Datum::BOOL(true)) => rhs,
+                    Literal(_) if matches!(rhs, Literal(_)) => fold_literal(LogExpr(
+                        Box::new(lhs),
+                        LogOp::And,
+                        Some(Box::new(rhs)),
+                    )),
+                    _ => LogExpr(Box::new(lhs), LogOp::And, Some(Box::new(rhs))),
+                }
+            }
+            LogExpr(lhs, LogOp::Or, Some(rhs)) => {
+                let lhs = lhs.fold_constants();
+                let rhs = rhs.fold_constants();
+                match &lhs {
+                    Literal(Datum::BOOL(false)) => rhs,
+                    Literal(_) if matches!(rhs, Literal(_)) => fold_literal(LogExpr(
+                        Box::new(lhs),
+                        LogOp::Or,
+                        Some(Box::new(rhs)),
+                    )),
+                    _ => LogExpr(Box::new(lhs), LogOp::Or, Some(Box::new(rhs))),
+                }
+            }
+            LogExpr(_, LogOp::Not, Some(_)) | LogExpr(_, LogOp::And | LogOp::Or, None) => {
+                panic!("Internal error: LogExpr arity does not match LogOp.")
             }
-            _ => unimplemented!(),
         }
     }
 }
@@ -171,4 +409,120 @@ mod tests {
         );
         println!("{}", e)
     }
+
+    #[test]
+    fn test_three_valued_logic() {
+        let row = Row::new(vec![]);
+        let t = || Box::new(Literal(Datum::BOOL(true)));
+        let f = || Box::new(Literal(Datum::BOOL(false)));
+        let n = || Box::new(Literal(Datum::NULL));
+
+        assert_eq!(LogExpr(t(), LogOp::And, Some(n())).eval(&row).unwrap(), Datum::NULL);
+        assert_eq!(LogExpr(f(), LogOp::And, Some(n())).eval(&row).unwrap(), Datum::BOOL(false));
+        assert_eq!(LogExpr(t(), LogOp::Or, Some(n())).eval(&row).unwrap(), Datum::BOOL(true));
+        assert_eq!(LogExpr(n(), LogOp::Or, Some(n())).eval(&row).unwrap(), Datum::NULL);
+        assert_eq!(LogExpr(n(), LogOp::Not, None).eval(&row).unwrap(), Datum::NULL);
+    }
+
+    #[test]
+    fn test_type_promotion_and_errors() {
+        let row = Row::new(vec![]);
+        let e = RelExpr(
+            Box::new(Literal(Datum::INT(3))),
+            RelOp::Lt,
+            Box::new(Literal(Datum::FLOAT(3.5))),
+        );
+        assert_eq!(e.eval(&row).unwrap(), Datum::BOOL(true));
+
+        let div_by_zero = ArithExpr(
+            Box::new(Literal(Datum::INT(1))),
+            ArithOp::Div,
+            Box::new(Literal(Datum::INT(0))),
+        );
+        assert_eq!(div_by_zero.eval(&row).unwrap_err(), EvalError::DivideByZero);
+
+        let bad_types = ArithExpr(
+            Box::new(Literal(Datum::STRING("a".to_owned()))),
+            ArithOp::Add,
+            Box::new(Literal(Datum::INT(1))),
+        );
+        assert!(matches!(
+            bad_types.eval(&row),
+            Err(EvalError::IncompatibleOperands(_))
+        ));
+    }
+
+    #[test]
+    fn test_fold_constants() {
+        // (1 + 2) > 30 folds straight down to a literal BOOL(false).
+        let e = RelExpr(
+            Box::new(ArithExpr(
+                Box::new(Literal(Datum::INT(1))),
+                ArithOp::Add,
+                Box::new(Literal(Datum::INT(2))),
+            )),
+            RelOp::Gt,
+            Box::new(Literal(Datum::INT(30))),
+        );
+        assert!(matches!(e.fold_constants(), Literal(Datum::BOOL(false))));
+
+        // $0 + 0 simplifies to $0 even though it can't be evaluated without a row.
+        let e = ArithExpr(Box::new(CID(0)), ArithOp::Add, Box::new(Literal(Datum::INT(0))));
+        assert!(matches!(e.fold_constants(), CID(0)));
+
+        // true && $0 simplifies to $0.
+        let e = LogExpr(
+            Box::new(Literal(Datum::BOOL(true))),
+            LogOp::And,
+            Some(Box::new(CID(0))),
+        );
+        assert!(matches!(e.fold_constants(), CID(0)));
+
+        // !!$0 simplifies to $0.
+        let e = LogExpr(
+            Box::new(LogExpr(Box::new(CID(0)), LogOp::Not, None)),
+            LogOp::Not,
+            None,
+        );
+        assert!(matches!(e.fold_constants(), CID(0)));
+
+        // 5 * 0 is all-literal, so it's safe to fold straight to the literal 0.
+        let e = ArithExpr(
+            Box::new(Literal(Datum::INT(5))),
+            ArithOp::Mul,
+            Box::new(Literal(Datum::INT(0))),
+        );
+        assert!(matches!(e.fold_constants(), Literal(Datum::INT(0))));
+
+        // $0 * 0 must NOT fold away $0: if column 0 turns out to be a STRING at eval time,
+        // folding to a bare literal 0 would silently swallow that type error.
+        let e = ArithExpr(Box::new(CID(0)), ArithOp::Mul, Box::new(Literal(Datum::INT(0))));
+        let folded = e.fold_constants();
+        let row = Row::new(vec![Datum::STRING("abc".to_owned())]);
+        assert!(matches!(
+            folded.eval(&row),
+            Err(EvalError::IncompatibleOperands(_))
+        ));
+    }
+
+    #[test]
+    fn test_explain() {
+        let e: Expr = RelExpr(
+            Box::new(ArithExpr(
+                Box::new(CID(0)),
+                ArithOp::Add,
+                Box::new(CID(1)),
+            )),
+            RelOp::Gt,
+            Box::new(Literal(Datum::INT(30))),
+        );
+        let expected = "\
+RelExpr >
+  ArithExpr +
+    CID 0
+    CID 1
+  Literal 30
+";
+        assert_eq!(e.explain(), expected);
+    }
 }
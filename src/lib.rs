@@ -0,0 +1,6 @@
+#![allow(warnings)]
+
+pub mod api;
+pub mod expr;
+pub mod row;
+pub mod serde;
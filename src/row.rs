@@ -0,0 +1,49 @@
+#![allow(warnings)]
+
+use std::fmt;
+
+/***************************************************************************************************/
+#[derive(Debug, Clone, PartialEq)]
+pub enum Datum {
+    NULL,
+    INT(i64),
+    FLOAT(f64),
+    STRING(String),
+    BOOL(bool),
+}
+
+impl fmt::Display for Datum {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Datum::NULL => write!(f, "NULL"),
+            Datum::INT(v) => write!(f, "{}", v),
+            Datum::FLOAT(v) => write!(f, "{}", v),
+            Datum::STRING(v) => write!(f, "{}", v),
+            Datum::BOOL(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/***************************************************************************************************/
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    cols: Vec<Datum>,
+}
+
+impl Row {
+    pub fn new(cols: Vec<Datum>) -> Row {
+        Row { cols }
+    }
+
+    pub fn get_column(&self, cid: usize) -> &Datum {
+        &self.cols[cid]
+    }
+
+    pub fn len(&self) -> usize {
+        self.cols.len()
+    }
+
+    pub fn columns(&self) -> &[Datum] {
+        &self.cols
+    }
+}
@@ -0,0 +1,284 @@
+#![allow(warnings)]
+
+//! Wire serialization for `Row`/`Datum`, used to ship partitions between nodes during a
+//! shuffle. The binary form is a compact, length-prefixed buffer (a tag byte per `Datum`
+//! variant, then its payload); `to_base64`/`to_hex` render that buffer as ASCII for logging
+//! or transport over text-only channels.
+
+use std::fmt;
+
+use crate::row::{Datum, Row};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SerdeError {
+    Truncated,
+    UnknownTag(u8),
+    InvalidUtf8,
+    InvalidEncoding,
+    TrailingBytes,
+}
+
+impl fmt::Display for SerdeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SerdeError::Truncated => write!(f, "buffer truncated"),
+            SerdeError::UnknownTag(tag) => write!(f, "unknown Datum tag byte: {}", tag),
+            SerdeError::InvalidUtf8 => write!(f, "STRING payload is not valid UTF-8"),
+            SerdeError::InvalidEncoding => write!(f, "invalid base64/hex text"),
+            SerdeError::TrailingBytes => write!(f, "trailing bytes after decoded value"),
+        }
+    }
+}
+
+const TAG_NULL: u8 = 0;
+const TAG_INT: u8 = 1;
+const TAG_FLOAT: u8 = 2;
+const TAG_STRING: u8 = 3;
+const TAG_BOOL: u8 = 4;
+
+fn read_bytes<'a>(buf: &'a [u8], pos: &mut usize, n: usize) -> Result<&'a [u8], SerdeError> {
+    let end = pos.checked_add(n).ok_or(SerdeError::Truncated)?;
+    let slice = buf.get(*pos..end).ok_or(SerdeError::Truncated)?;
+    *pos = end;
+    Ok(slice)
+}
+
+fn read_u32(buf: &[u8], pos: &mut usize) -> Result<u32, SerdeError> {
+    let bytes = read_bytes(buf, pos, 4)?;
+    Ok(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_i64(buf: &[u8], pos: &mut usize) -> Result<i64, SerdeError> {
+    let bytes = read_bytes(buf, pos, 8)?;
+    Ok(i64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_f64(buf: &[u8], pos: &mut usize) -> Result<f64, SerdeError> {
+    let bytes = read_bytes(buf, pos, 8)?;
+    Ok(f64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn encode_datum(d: &Datum, buf: &mut Vec<u8>) {
+    match d {
+        Datum::NULL => buf.push(TAG_NULL),
+        Datum::INT(v) => {
+            buf.push(TAG_INT);
+            buf.extend_from_slice(&v.to_le_bytes());
+        }
+        Datum::FLOAT(v) => {
+            buf.push(TAG_FLOAT);
+            buf.extend_from_slice(&v.to_le_bytes());
+        }
+        Datum::STRING(s) => {
+            buf.push(TAG_STRING);
+            let bytes = s.as_bytes();
+            buf.extend_from_slice(&(bytes.len() as u32).to_le_bytes());
+            buf.extend_from_slice(bytes);
+        }
+        Datum::BOOL(b) => {
+            buf.push(TAG_BOOL);
+            buf.push(*b as u8);
+        }
+    }
+}
+
+fn decode_datum(buf: &[u8], pos: &mut usize) -> Result<Datum, SerdeError> {
+    let tag = *read_bytes(buf, pos, 1)?.first().unwrap();
+    match tag {
+        TAG_NULL => Ok(Datum::NULL),
+        TAG_INT => Ok(Datum::INT(read_i64(buf, pos)?)),
+        TAG_FLOAT => Ok(Datum::FLOAT(read_f64(buf, pos)?)),
+        TAG_STRING => {
+            let len = read_u32(buf, pos)? as usize;
+            let bytes = read_bytes(buf, pos, len)?;
+            let s = std::str::from_utf8(bytes)
+                .map_err(|_| SerdeError::InvalidUtf8)?
+                .to_owned();
+            Ok(Datum::STRING(s))
+        }
+        TAG_BOOL => Ok(Datum::BOOL(read_bytes(buf, pos, 1)?[0] != 0)),
+        other => Err(SerdeError::UnknownTag(other)),
+    }
+}
+
+fn encode_row_into(row: &Row, buf: &mut Vec<u8>) {
+    buf.extend_from_slice(&(row.len() as u32).to_le_bytes());
+    for d in row.columns() {
+        encode_datum(d, buf);
+    }
+}
+
+fn decode_row_at(buf: &[u8], pos: &mut usize) -> Result<Row, SerdeError> {
+    let ncols = read_u32(buf, pos)? as usize;
+    let mut cols = Vec::with_capacity(ncols);
+    for _ in 0..ncols {
+        cols.push(decode_datum(buf, pos)?);
+    }
+    Ok(Row::new(cols))
+}
+
+pub fn encode_row(row: &Row) -> Vec<u8> {
+    let mut buf = Vec::new();
+    encode_row_into(row, &mut buf);
+    buf
+}
+
+pub fn decode_row(buf: &[u8]) -> Result<Row, SerdeError> {
+    let mut pos = 0;
+    let row = decode_row_at(buf, &mut pos)?;
+    if pos != buf.len() {
+        return Err(SerdeError::TrailingBytes);
+    }
+    Ok(row)
+}
+
+pub fn encode_partition(rows: &[Row]) -> Vec<u8> {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&(rows.len() as u32).to_le_bytes());
+    for row in rows {
+        let mut row_buf = Vec::new();
+        encode_row_into(row, &mut row_buf);
+        buf.extend_from_slice(&(row_buf.len() as u32).to_le_bytes());
+        buf.extend_from_slice(&row_buf);
+    }
+    buf
+}
+
+pub fn decode_partition(buf: &[u8]) -> Result<Vec<Row>, SerdeError> {
+    let mut pos = 0;
+    let nrows = read_u32(buf, &mut pos)? as usize;
+    let mut rows = Vec::with_capacity(nrows);
+    for _ in 0..nrows {
+        let row_len = read_u32(buf, &mut pos)? as usize;
+        let row_buf = read_bytes(buf, &mut pos, row_len)?;
+        rows.push(decode_row(row_buf)?);
+    }
+    if pos != buf.len() {
+        return Err(SerdeError::TrailingBytes);
+    }
+    Ok(rows)
+}
+
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub fn to_base64(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = *chunk.get(1).unwrap_or(&0);
+        let b2 = *chunk.get(2).unwrap_or(&0);
+        let n = ((b0 as u32) << 16) | ((b1 as u32) << 8) | (b2 as u32);
+        out.push(BASE64_ALPHABET[((n >> 18) & 0x3f) as usize] as char);
+        out.push(BASE64_ALPHABET[((n >> 12) & 0x3f) as usize] as char);
+        out.push(if chunk.len() > 1 {
+            BASE64_ALPHABET[((n >> 6) & 0x3f) as usize] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            BASE64_ALPHABET[(n & 0x3f) as usize] as char
+        } else {
+            '='
+        });
+    }
+    out
+}
+
+fn base64_val(c: u8) -> Result<u32, SerdeError> {
+    match c {
+        b'A'..=b'Z' => Ok((c - b'A') as u32),
+        b'a'..=b'z' => Ok((c - b'a') as u32 + 26),
+        b'0'..=b'9' => Ok((c - b'0') as u32 + 52),
+        b'+' => Ok(62),
+        b'/' => Ok(63),
+        _ => Err(SerdeError::InvalidEncoding),
+    }
+}
+
+pub fn from_base64(s: &str) -> Result<Vec<u8>, SerdeError> {
+    let trimmed = s.trim_end_matches('=');
+    let bytes = trimmed.as_bytes();
+    if bytes.len() % 4 == 1 {
+        return Err(SerdeError::InvalidEncoding);
+    }
+    let mut out = Vec::with_capacity(bytes.len() * 3 / 4);
+    for chunk in bytes.chunks(4) {
+        let mut n: u32 = 0;
+        for (i, &c) in chunk.iter().enumerate() {
+            n |= base64_val(c)? << (18 - 6 * i);
+        }
+        out.push((n >> 16) as u8);
+        if chunk.len() > 2 {
+            out.push((n >> 8) as u8);
+        }
+        if chunk.len() > 3 {
+            out.push(n as u8);
+        }
+    }
+    Ok(out)
+}
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub fn from_hex(s: &str) -> Result<Vec<u8>, SerdeError> {
+    if s.len() % 2 != 0 {
+        return Err(SerdeError::InvalidEncoding);
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).map_err(|_| SerdeError::InvalidEncoding))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_row_roundtrip() {
+        let row = Row::new(vec![
+            Datum::INT(42),
+            Datum::FLOAT(1.5),
+            Datum::STRING("hi".to_owned()),
+            Datum::BOOL(true),
+            Datum::NULL,
+        ]);
+        let encoded = encode_row(&row);
+        let decoded = decode_row(&encoded).unwrap();
+        assert_eq!(decoded.columns(), row.columns());
+    }
+
+    #[test]
+    fn test_partition_roundtrip() {
+        let rows = vec![
+            Row::new(vec![Datum::INT(1)]),
+            Row::new(vec![Datum::STRING("row2".to_owned())]),
+        ];
+        let encoded = encode_partition(&rows);
+        let decoded = decode_partition(&encoded).unwrap();
+        assert_eq!(decoded.len(), rows.len());
+        assert_eq!(decoded[0].columns(), rows[0].columns());
+        assert_eq!(decoded[1].columns(), rows[1].columns());
+    }
+
+    #[test]
+    fn test_base64_and_hex_roundtrip() {
+        let row = Row::new(vec![Datum::INT(7), Datum::STRING("flare".to_owned())]);
+        let encoded = encode_row(&row);
+
+        let b64 = to_base64(&encoded);
+        assert_eq!(from_base64(&b64).unwrap(), encoded);
+
+        let hex = to_hex(&encoded);
+        assert_eq!(from_hex(&hex).unwrap(), encoded);
+    }
+
+    #[test]
+    fn test_truncated_and_unknown_tag() {
+        assert_eq!(decode_row(&[]), Err(SerdeError::Truncated));
+        assert_eq!(decode_row(&[1, 0, 0, 0, 99]), Err(SerdeError::UnknownTag(99)));
+    }
+}